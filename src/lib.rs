@@ -3,8 +3,11 @@ use eframe::{
     egui,
     egui::{Color32, RichText},
 };
+use egui_plot::{Line, Plot, PlotPoints, Points};
 use instant::Instant;
-use rand::Rng;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
 use std::cmp::{max, min};
 use std::time::Duration;
 // use std::time::{Duration, Instant};
@@ -13,13 +16,41 @@ use std::time::Duration;
 const X_SIZE: usize = 37;
 const Y_SIZE: usize = 33;
 
+// How often (in sim seconds) the compartment counts are sampled into `history`.
+const HISTORY_SAMPLE_INTERVAL_SECS: f32 = 0.2;
+// Cap on the number of samples kept, so a long-running session doesn't grow unbounded.
+const HISTORY_MAX_SAMPLES: usize = 4000;
+// Frame duration (in seconds, before the Speed slider scales it) that a single
+// Step advances, matching the usual frame cadence.
+const STEP_SECS: f32 = 0.016;
+// Clamp on per-frame wall-clock delta, so e.g. a backgrounded tab doesn't dump a
+// huge backlog of scheduled transitions the instant it regains focus.
+const MAX_FRAME_DT_SECS: f32 = 0.25;
+
+#[derive(Clone, Copy, Debug)]
+struct CompartmentCounts {
+    t: f32,
+    susceptible: usize,
+    exposed: usize,
+    infected: usize,
+    recovered: usize,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum CellState {
+    Susceptible,
+    Exposed,
+    Infected,
+    Recovered,
+}
+
 #[derive(Clone, Copy, Debug)]
 struct Cell {
     vaccinated: bool,
-    infected: bool,
+    state: CellState,
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
 struct Params {
     vac_left: i32,  // 0..100
     vac_right: i32, // 0..100
@@ -27,6 +58,10 @@ struct Params {
     inf_rate_nonvac: i32, // 0..100
     inf_rate_vac: i32,    // 0..100
     inf_speed: f32,       // 0.1..10+ (multiplier)
+    incubation_period: f32, // 0.1..10+ (multiplier, same units as inf_speed)
+    infectious_period: f32, // 0.1..10+ (multiplier, same units as inf_speed)
+    immunity_loss_period: f32, // 0.1..10+ (multiplier, same units as inf_speed)
+    speed: f32, // 0.25..16 (global sim-time speed multiplier)
 }
 
 impl Default for Params {
@@ -38,35 +73,78 @@ impl Default for Params {
             inf_rate_nonvac: 90,
             inf_rate_vac: 10,
             inf_speed: 5.0,
+            incubation_period: 3.0,
+            infectious_period: 2.0,
+            immunity_loss_period: 1.0,
+            speed: 1.0,
         }
     }
 }
 
+// Everything needed to replay a run exactly: the PRNG seed, the sliders, and the
+// cells a user manually clicked to seed an outbreak (spread is deterministic
+// given those, so it isn't stored itself). Each seed click is tagged with the
+// sim-time it happened at, so loading re-injects it at the same clock position
+// instead of dumping every seed at t=0. Only the slider values at save time are
+// stored, and Clear Infections keeps drawing from the already-advanced PRNG, so
+// a run whose sliders changed after it started, or whose infections were
+// cleared, replays differently.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct Scenario {
+    seed: u64,
+    params: Params,
+    seed_infections: Vec<(usize, usize, f32)>,
+}
+
 #[derive(Clone, Copy, Debug)]
-struct ScheduledInfection {
+struct ScheduledTransition {
     x: usize,
     y: usize,
-    trigger_at: Instant,
+    next_state: CellState,
+    // Sim-time (seconds since the current run started) at which this fires.
+    trigger_at: f32,
+    // Monotonic order of scheduling, used as a stable tiebreak so that firing
+    // order — and therefore the sequence of `self.rng` draws — is independent
+    // of how many transitions a given frame happens to batch together.
+    seq: u64,
 }
 
 pub struct App {
     grid: Vec<Cell>,
     params: Params,
-    scheduled: Vec<ScheduledInfection>,
+    scheduled: Vec<ScheduledTransition>,
     total_vaccinated: usize,
     // cached colors (to mirror the JS idea of color-coding)
     color_vax: Color32,
     color_unvax: Color32,
+    color_exposed: Color32,
     color_infected: Color32,
+    color_recovered: Color32,
+    // epidemic curve
+    history: Vec<CompartmentCounts>,
+    last_history_sample_t: f32,
+    // transport controls
+    running: bool,
+    sim_time: f32,
+    last_frame: Instant,
+    // determinism / scenario sharing
+    rng: StdRng,
+    seed: u64,
+    seed_text: String,
+    seed_infections: Vec<(usize, usize, f32)>,
+    scenario_text: String,
+    scenario_error: Option<String>,
+    next_seq: u64,
 }
 
 impl App {
     pub fn new(_cc: &eframe::CreationContext<'_>) -> Self {
+        let seed = rand::rng().random::<u64>();
         let mut app = Self {
             grid: vec![
                 Cell {
                     vaccinated: false,
-                    infected: false
+                    state: CellState::Susceptible,
                 };
                 X_SIZE * Y_SIZE
             ],
@@ -76,19 +154,183 @@ impl App {
             color_vax: Color32::from_hex("#8babf1").unwrap(),
             // Blue options: 8F7DE8 (darker), ADA0EE (lighter)
             color_unvax: Color32::from_hex("#c44601").unwrap(),
+            color_exposed: Color32::from_hex("#e8c547").unwrap(),
             color_infected: Color32::from_hex("#200024").unwrap(),
+            color_recovered: Color32::from_hex("#4a8b3a").unwrap(),
+            history: Vec::new(),
+            last_history_sample_t: 0.0,
+            running: true,
+            sim_time: 0.0,
+            last_frame: Instant::now(),
+            rng: StdRng::seed_from_u64(seed),
+            seed,
+            seed_text: seed.to_string(),
+            seed_infections: Vec::new(),
+            scenario_text: String::new(),
+            scenario_error: None,
+            next_seq: 0,
         };
         app.populate();
+        app.reset_history();
         app
     }
 
+    // Clears infections, re-populates vaccination from the current seed, and
+    // restarts the sim clock.
+    fn restart(&mut self) {
+        self.rng = StdRng::seed_from_u64(self.seed);
+        self.populate();
+        self.sim_time = 0.0;
+        self.next_seq = 0;
+        self.seed_infections.clear();
+        self.reset_history();
+    }
+
+    // Re-seeds the PRNG and restarts, so the new seed actually takes effect.
+    fn reseed(&mut self, seed: u64) {
+        self.seed = seed;
+        self.seed_text = seed.to_string();
+        self.rng = StdRng::seed_from_u64(seed);
+        self.restart();
+    }
+
+    fn save_scenario(&mut self) {
+        let scenario = Scenario {
+            seed: self.seed,
+            params: self.params,
+            seed_infections: self.seed_infections.clone(),
+        };
+        self.scenario_text =
+            serde_json::to_string_pretty(&scenario).unwrap_or_else(|e| format!("error: {e}"));
+        self.scenario_error = None;
+    }
+
+    fn load_scenario(&mut self) {
+        match serde_json::from_str::<Scenario>(&self.scenario_text) {
+            Ok(scenario) => {
+                self.params = scenario.params;
+                self.reseed(scenario.seed);
+                // reseed() restarts at sim_time 0, so re-inject each seed click at
+                // the same clock position it originally happened at, rather than
+                // firing them all immediately.
+                for (x, y, t) in scenario.seed_infections {
+                    let seq = self.next_seq;
+                    self.next_seq += 1;
+                    self.scheduled.push(ScheduledTransition {
+                        x,
+                        y,
+                        next_state: CellState::Exposed,
+                        trigger_at: t.max(0.0),
+                        seq,
+                    });
+                    self.seed_infections.push((x, y, t));
+                }
+                self.scenario_error = None;
+            }
+            Err(e) => {
+                self.scenario_error = Some(format!("failed to load scenario: {e}"));
+            }
+        }
+    }
+
+    fn reset_history(&mut self) {
+        self.history.clear();
+        self.last_history_sample_t = self.sim_time;
+        self.sample_history();
+    }
+
+    fn sample_history(&mut self) {
+        let t = self.sim_time;
+        let mut susceptible = 0;
+        let mut exposed = 0;
+        let mut infected = 0;
+        let mut recovered = 0;
+        for c in &self.grid {
+            match c.state {
+                CellState::Susceptible => susceptible += 1,
+                CellState::Exposed => exposed += 1,
+                CellState::Infected => infected += 1,
+                CellState::Recovered => recovered += 1,
+            }
+        }
+        self.history.push(CompartmentCounts {
+            t,
+            susceptible,
+            exposed,
+            infected,
+            recovered,
+        });
+        if self.history.len() > HISTORY_MAX_SAMPLES {
+            let excess = self.history.len() - HISTORY_MAX_SAMPLES;
+            self.history.drain(0..excess);
+        }
+    }
+
+    fn maybe_sample_history(&mut self) {
+        if self.sim_time - self.last_history_sample_t >= HISTORY_SAMPLE_INTERVAL_SECS {
+            self.last_history_sample_t = self.sim_time;
+            self.sample_history();
+        }
+    }
+
+    fn draw_epidemic_curve(&self, ui: &mut egui::Ui) {
+        let susceptible: PlotPoints = self
+            .history
+            .iter()
+            .map(|c| [c.t as f64, c.susceptible as f64])
+            .collect();
+        let exposed: PlotPoints = self
+            .history
+            .iter()
+            .map(|c| [c.t as f64, c.exposed as f64])
+            .collect();
+        let infected: PlotPoints = self
+            .history
+            .iter()
+            .map(|c| [c.t as f64, c.infected as f64])
+            .collect();
+        let recovered: PlotPoints = self
+            .history
+            .iter()
+            .map(|c| [c.t as f64, c.recovered as f64])
+            .collect();
+
+        let peak = self.history.iter().max_by_key(|c| c.infected);
+
+        Plot::new("epidemic_curve")
+            .height(180.0)
+            .show_axes([true, true])
+            .allow_scroll(false)
+            .legend(egui_plot::Legend::default())
+            .show(ui, |plot_ui| {
+                plot_ui.line(Line::new("Susceptible", susceptible).color(self.color_vax));
+                plot_ui.line(Line::new("Exposed", exposed).color(self.color_exposed));
+                plot_ui.line(Line::new("Infected", infected).color(self.color_infected));
+                plot_ui.line(Line::new("Recovered", recovered).color(self.color_recovered));
+                if let Some(peak) = peak {
+                    if peak.infected > 0 {
+                        let point = [[peak.t as f64, peak.infected as f64]];
+                        plot_ui.points(
+                            Points::new("Peak infected", PlotPoints::from(point.to_vec()))
+                                .radius(4.0)
+                                .color(self.color_infected),
+                        );
+                        plot_ui.text(egui_plot::Text::new(
+                            "peak_infected_label",
+                            egui_plot::PlotPoint::new(peak.t as f64, peak.infected as f64),
+                            format!("peak: {}", peak.infected),
+                        ));
+                    }
+                }
+            });
+    }
+
     fn idx(x: usize, y: usize) -> usize {
         y * X_SIZE + x
     }
 
     fn populate(&mut self) {
         self.scheduled.clear();
-        let mut rng = rand::rng();
         let vac_left = self.params.vac_left as f64 / 100.0;
         let vac_right = if self.params.right_same {
             self.params.vac_left as f64 / 100.0
@@ -100,14 +342,14 @@ impl App {
         for x in 0..X_SIZE {
             for y in 0..Y_SIZE {
                 let vaccinated = if x < X_SIZE / 2 {
-                    rng.random::<f64>() < vac_left
+                    self.rng.random::<f64>() < vac_left
                 } else {
-                    rng.random::<f64>() < vac_right
+                    self.rng.random::<f64>() < vac_right
                 };
                 let idx = Self::idx(x, y);
                 self.grid[idx] = Cell {
                     vaccinated,
-                    infected: false,
+                    state: CellState::Susceptible,
                 };
                 if vaccinated {
                     self.total_vaccinated += 1;
@@ -120,11 +362,15 @@ impl App {
         let total_pop = (X_SIZE * Y_SIZE) as f32;
         let total_vax = self.total_vaccinated as f32;
         let total_unvax = total_pop - total_vax;
-        let num_infected = self.grid.iter().filter(|c| c.infected).count() as f32;
+        let num_infected = self
+            .grid
+            .iter()
+            .filter(|c| c.state == CellState::Infected)
+            .count() as f32;
         let num_vax_infected = self
             .grid
             .iter()
-            .filter(|c| c.infected && c.vaccinated)
+            .filter(|c| c.state == CellState::Infected && c.vaccinated)
             .count() as f32;
         let num_unvax_infected = num_infected - num_vax_infected;
 
@@ -160,19 +406,9 @@ impl App {
         )
     }
 
-    fn schedule_infection(&mut self, x: usize, y: usize, delay_ms: u64) {
-        let trigger_at = Instant::now() + Duration::from_millis(delay_ms);
-        self.scheduled.push(ScheduledInfection { x, y, trigger_at });
-    }
-
-    fn try_infect(&mut self, x: usize, y: usize) {
-        let idx = Self::idx(x, y);
-        if self.grid[idx].infected {
-            return;
-        }
-        self.grid[idx].infected = true;
-
-        // After infecting, consider neighbors with probability depending on vaccination status
+    // Bounding box (inclusive) of the Moore neighborhood around (x, y), clamped
+    // to the grid edges.
+    fn neighborhood_bounds(x: usize, y: usize) -> (usize, usize, usize, usize) {
         let (sx, ex) = (
             max(0, x as isize - 1) as usize,
             min(X_SIZE as isize - 1, x as isize + 1) as usize,
@@ -181,40 +417,180 @@ impl App {
             max(0, y as isize - 1) as usize,
             min(Y_SIZE as isize - 1, y as isize + 1) as usize,
         );
-        let mut rng = rand::rng();
+        (sx, ex, sy, ey)
+    }
+
+    // Average size of the Moore neighborhood (up to 8 cells) over every grid
+    // position, so edge and corner cells — which have fewer neighbors — pull the
+    // average down rather than being ignored.
+    fn mean_neighbor_count() -> f32 {
+        let mut total = 0usize;
+        for x in 0..X_SIZE {
+            for y in 0..Y_SIZE {
+                let (sx, ex, sy, ey) = Self::neighborhood_bounds(x, y);
+                total += (ex - sx + 1) * (ey - sy + 1) - 1; // exclude self
+            }
+        }
+        total as f32 / (X_SIZE * Y_SIZE) as f32
+    }
+
+    // Expected secondary infections from one infectious unvaccinated cell dropped
+    // into an otherwise fully susceptible field.
+    fn r0(&self) -> f32 {
+        Self::mean_neighbor_count() * (self.params.inf_rate_nonvac as f32 / 100.0)
+    }
+
+    // Critical vaccination fraction needed for herd immunity, adjusted for
+    // imperfect vaccine efficacy. `None` means the vaccine isn't effective enough
+    // to ever reach herd immunity at the current infection rates.
+    fn herd_immunity_threshold(&self, r0: f32) -> Option<f32> {
+        if r0 <= 1.0 {
+            return Some(0.0);
+        }
+        let efficacy =
+            1.0 - self.params.inf_rate_vac as f32 / self.params.inf_rate_nonvac as f32;
+        if efficacy <= 0.0 {
+            return None;
+        }
+        let p_c = ((1.0 - 1.0 / r0) / efficacy).max(0.0);
+        if p_c > 1.0 {
+            return None;
+        }
+        Some(p_c)
+    }
+
+    fn schedule_transition(&mut self, x: usize, y: usize, next_state: CellState, delay_ms: u64) {
+        let trigger_at = self.sim_time + delay_ms as f32 / 1000.0;
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        self.scheduled.push(ScheduledTransition {
+            x,
+            y,
+            next_state,
+            trigger_at,
+            seq,
+        });
+    }
+
+    // Sample a delay (ms) from `base..base+spread`, rescaled by a speed-multiplier.
+    fn sample_delay_ms(base_ms: f32, spread_ms: f32, speed: f32, rng: &mut impl Rng) -> u64 {
+        let base = base_ms + spread_ms * rng.random::<f32>();
+        let speed = speed.max(0.01);
+        (base / speed) as u64
+    }
+
+    // Converts a per-stage *period* (incubation/infectious/immunity-loss — larger
+    // means the stage lasts longer) into the *speed* that `sample_delay_ms`
+    // expects (larger means the stage resolves faster), then folds in the
+    // `inf_speed` master multiplier that rescales every timer at once.
+    fn stage_speed(&self, period: f32) -> f32 {
+        self.params.inf_speed / period.max(0.01)
+    }
+
+    fn try_infect(&mut self, x: usize, y: usize) {
+        let idx = Self::idx(x, y);
+        if self.grid[idx].state != CellState::Susceptible {
+            return;
+        }
+        self.grid[idx].state = CellState::Exposed;
+
+        let speed = self.stage_speed(self.params.incubation_period);
+        let delay = Self::sample_delay_ms(500.0, 2500.0, speed, &mut self.rng);
+        self.schedule_transition(x, y, CellState::Infected, delay);
+    }
+
+    fn become_infected(&mut self, x: usize, y: usize) {
+        let idx = Self::idx(x, y);
+        if self.grid[idx].state != CellState::Exposed {
+            return;
+        }
+        self.grid[idx].state = CellState::Infected;
+
+        // Now infectious: consider neighbors with probability depending on vaccination status
+        let (sx, ex, sy, ey) = Self::neighborhood_bounds(x, y);
         for ix in sx..=ex {
             for iy in sy..=ey {
                 let ii = Self::idx(ix, iy);
-                if self.grid[ii].infected {
+                if self.grid[ii].state != CellState::Susceptible {
                     continue;
                 }
                 let chance = if self.grid[ii].vaccinated {
                     self.params.inf_rate_vac as f64 / 100.0
                 } else {
                     self.params.inf_rate_nonvac as f64 / 100.0
-                } as f64;
-                if rng.random::<f64>() < chance {
-                    let base_ms: f32 = 500.0 + 5000.0 * rng.random::<f32>();
-                    let speed = self.params.inf_speed.max(0.01);
-                    let delay = (base_ms / speed) as u64;
-                    self.schedule_infection(ix, iy, delay);
+                };
+                if self.rng.random::<f64>() < chance {
+                    self.try_infect(ix, iy);
                 }
             }
         }
+
+        let speed = self.stage_speed(self.params.infectious_period);
+        let delay = Self::sample_delay_ms(500.0, 5000.0, speed, &mut self.rng);
+        self.schedule_transition(x, y, CellState::Recovered, delay);
+    }
+
+    fn become_recovered(&mut self, x: usize, y: usize) {
+        let idx = Self::idx(x, y);
+        if self.grid[idx].state != CellState::Infected {
+            return;
+        }
+        self.grid[idx].state = CellState::Recovered;
+
+        let speed = self.stage_speed(self.params.immunity_loss_period);
+        let delay = Self::sample_delay_ms(1000.0, 8000.0, speed, &mut self.rng);
+        self.schedule_transition(x, y, CellState::Susceptible, delay);
+    }
+
+    fn become_susceptible(&mut self, x: usize, y: usize) {
+        let idx = Self::idx(x, y);
+        if self.grid[idx].state != CellState::Recovered {
+            return;
+        }
+        self.grid[idx].state = CellState::Susceptible;
+    }
+
+    fn apply_transition(&mut self, x: usize, y: usize, next_state: CellState) {
+        match next_state {
+            CellState::Susceptible => self.become_susceptible(x, y),
+            CellState::Exposed => self.try_infect(x, y),
+            CellState::Infected => self.become_infected(x, y),
+            CellState::Recovered => self.become_recovered(x, y),
+        }
+    }
+
+    // Advances the sim clock by `dt_secs` (already speed-scaled) and lets
+    // everything keyed off `sim_time` — scheduled transitions, history sampling —
+    // catch up. Used both by the per-frame tick and by a manual Step.
+    fn advance_sim(&mut self, dt_secs: f32) {
+        self.sim_time += dt_secs;
+        self.update_scheduled();
+        self.maybe_sample_history();
     }
 
     fn update_scheduled(&mut self) {
-        let now = Instant::now();
-        // Partition into ready and pending
-        let mut i = 0;
-        while i < self.scheduled.len() {
-            if self.scheduled[i].trigger_at <= now {
-                let s = self.scheduled.remove(i);
-                self.try_infect(s.x, s.y);
-            } else {
-                i += 1;
-            }
+        // Fire due transitions one at a time in a fixed order (trigger_at, then
+        // scheduling order), with the clock set to each one's trigger_at while it
+        // runs, so follow-ups are timed from when it fired and any that fall due
+        // within this frame fire here too — otherwise two runs that advance
+        // sim_time in different-sized steps would fire transitions (and therefore
+        // draw from `self.rng`) in different orders and diverge even with the
+        // same seed.
+        let target = self.sim_time;
+        loop {
+            let next = self
+                .scheduled
+                .iter()
+                .enumerate()
+                .filter(|(_, s)| s.trigger_at <= target)
+                .min_by(|(_, a), (_, b)| a.trigger_at.total_cmp(&b.trigger_at).then(a.seq.cmp(&b.seq)))
+                .map(|(i, _)| i);
+            let Some(i) = next else { break };
+            let s = self.scheduled.swap_remove(i);
+            self.sim_time = s.trigger_at;
+            self.apply_transition(s.x, s.y, s.next_state);
         }
+        self.sim_time = target;
     }
 
     fn draw_grid(&mut self, ui: &mut egui::Ui) {
@@ -234,15 +610,20 @@ impl App {
                 let idx = Self::idx(x, y);
                 let cell = self.grid[idx];
 
-                // Cell color
-                let mut fill_color = if cell.vaccinated {
-                    self.color_vax
-                } else {
-                    self.color_unvax
+                // Cell color: susceptible cells are colored by vaccination status;
+                // every other compartment gets its own dedicated color.
+                let fill_color = match cell.state {
+                    CellState::Susceptible => {
+                        if cell.vaccinated {
+                            self.color_vax
+                        } else {
+                            self.color_unvax
+                        }
+                    }
+                    CellState::Exposed => self.color_exposed,
+                    CellState::Infected => self.color_infected,
+                    CellState::Recovered => self.color_recovered,
                 };
-                if cell.infected {
-                    fill_color = self.color_infected;
-                }
 
                 // Compute rect for this cell
                 let min = rect.min + egui::vec2(x as f32 * full_size, y as f32 * full_size);
@@ -267,8 +648,11 @@ impl App {
                 // Stroke border (always on top)
                 painter.rect_stroke(r, 0.0, (border, Color32::BLACK), egui::StrokeKind::Inside);
 
-                // Infect if clicked
-                if response.clicked() {
+                // Infect if clicked. Record it, tagged with the current sim-time, as
+                // a seed infection (not spread-induced) so scenario save/load can
+                // re-inject it at the same clock position.
+                if response.clicked() && self.grid[idx].state == CellState::Susceptible {
+                    self.seed_infections.push((x, y, self.sim_time));
                     self.try_infect(x, y);
                 }
             }
@@ -287,16 +671,78 @@ impl eframe::App for App {
         ctx.set_style(style);
         //ctx.set_visuals(egui::Visuals::dark());
 
-        // progress scheduled infections
-        self.update_scheduled();
+        // Advance the sim clock by wall-clock dt, scaled by the speed slider, but
+        // only while running — this is what lets Pause freeze the grid instead of
+        // firing a backlog of scheduled transitions the moment focus returns.
+        let now = Instant::now();
+        let frame_dt = (now - self.last_frame).as_secs_f32().min(MAX_FRAME_DT_SECS);
+        self.last_frame = now;
+        if self.running {
+            self.advance_sim(frame_dt * self.params.speed);
+        }
 
         egui::TopBottomPanel::top("top").show(ctx, |ui| {
             ui.heading("Herd Immunity Simulator");
+            ui.horizontal(|ui| {
+                let play_label = if self.running { "Pause" } else { "Play" };
+                if ui.button(play_label).clicked() {
+                    self.running = !self.running;
+                }
+                ui.add_enabled_ui(!self.running, |ui| {
+                    if ui.button("Step").clicked() {
+                        self.advance_sim(STEP_SECS * self.params.speed);
+                    }
+                });
+                if ui.button("Restart").clicked() {
+                    self.restart();
+                }
+                ui.add(egui::Slider::new(&mut self.params.speed, 0.25..=16.0).text("Speed"));
+            });
         });
 
         egui::SidePanel::left("controls").resizable(false).show(ctx, |ui| {
             ui.style_mut().text_styles.get_mut(&egui::TextStyle::Body).unwrap().size = 17.0;
             ui.style_mut().text_styles.get_mut(&egui::TextStyle::Button).unwrap().size = 17.0;
+
+            ui.label(RichText::new("Randomization").strong());
+            ui.horizontal(|ui| {
+                ui.label("Seed:");
+                let seed_resp =
+                    ui.add(egui::TextEdit::singleline(&mut self.seed_text).desired_width(140.0));
+                let enter_committed =
+                    seed_resp.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                let apply_clicked = ui.button("Apply").clicked();
+                if enter_committed || apply_clicked {
+                    match self.seed_text.parse::<u64>() {
+                        Ok(seed) => self.reseed(seed),
+                        Err(_) => self.seed_text = self.seed.to_string(),
+                    }
+                }
+                if ui.button("Randomize seed").clicked() {
+                    let seed = rand::rng().random::<u64>();
+                    self.reseed(seed);
+                }
+            });
+            ui.collapsing("Scenario (save/load)", |ui| {
+                ui.horizontal(|ui| {
+                    if ui.button("Save scenario").clicked() {
+                        self.save_scenario();
+                    }
+                    if ui.button("Load scenario").clicked() {
+                        self.load_scenario();
+                    }
+                });
+                ui.add(
+                    egui::TextEdit::multiline(&mut self.scenario_text)
+                        .desired_rows(6)
+                        .hint_text("Paste a saved scenario JSON here, or click Save scenario"),
+                );
+                if let Some(err) = &self.scenario_error {
+                    ui.colored_label(Color32::from_rgb(200, 40, 40), err);
+                }
+            });
+            ui.separator();
+
             ui.label(RichText::new("Vaccination Rates").strong());
             ui.add(egui::Slider::new(&mut self.params.vac_left, 0..=100).text("Left half"));
             ui.horizontal(|ui| {
@@ -306,16 +752,57 @@ impl eframe::App for App {
             ui.add_enabled_ui(!self.params.right_same, |ui| {
                 ui.add(egui::Slider::new(&mut self.params.vac_right, 0..=100).text("Right half"));
             });
-            if ui.button("Populate").clicked() { self.populate(); }
+            if ui.button("Populate").clicked() {
+                // Draw a fresh seed rather than just re-populating from the
+                // already-advanced RNG: otherwise the displayed grid no longer
+                // matches `self.seed`, and a scenario saved after repeated
+                // Populate clicks wouldn't reproduce what's on screen.
+                let seed = rand::rng().random::<u64>();
+                self.reseed(seed);
+            }
             ui.separator();
 
             ui.label(RichText::new("Infection Parameters").strong());
             ui.add(egui::Slider::new(&mut self.params.inf_rate_nonvac, 0..=100).text("Infection rate (unvaccinated)"));
             ui.add(egui::Slider::new(&mut self.params.inf_rate_vac, 0..=100).text("Infection rate (vaccinated)"));
             ui.add(egui::Slider::new(&mut self.params.inf_speed, 0.5..=10.0).text("Infection speed (multiplier)"));
-            if ui.button("Clear Infections").clicked() {
-                for c in &mut self.grid { c.infected = false; }
-                self.scheduled.clear();
+            ui.add(egui::Slider::new(&mut self.params.incubation_period, 0.1..=10.0).text("Incubation period (multiplier)"));
+            ui.add(egui::Slider::new(&mut self.params.infectious_period, 0.1..=10.0).text("Infectious period (multiplier)"));
+            ui.add(egui::Slider::new(&mut self.params.immunity_loss_period, 0.1..=10.0).text("Immunity loss period (multiplier)"));
+            ui.horizontal(|ui| {
+                if ui.button("Clear Infections").clicked() {
+                    for c in &mut self.grid { c.state = CellState::Susceptible; }
+                    self.scheduled.clear();
+                    self.seed_infections.clear();
+                }
+                if ui.button("Reset history").clicked() {
+                    self.reset_history();
+                }
+            });
+            ui.separator();
+
+            ui.label(RichText::new("Herd Immunity").strong());
+            let r0 = self.r0();
+            let p_c = self.herd_immunity_threshold(r0);
+            let current_vax_fraction = self.total_vaccinated as f32 / (X_SIZE * Y_SIZE) as f32;
+            ui.label(format!("R₀ (basic reproduction number): {:.2}", r0));
+            match p_c {
+                Some(p_c) => {
+                    ui.label(format!("Herd immunity threshold: {:.1}%", p_c * 100.0));
+                    let above = current_vax_fraction >= p_c;
+                    let (text, color) = if above {
+                        ("Above herd immunity threshold", Color32::from_rgb(40, 140, 40))
+                    } else {
+                        ("Below herd immunity threshold", Color32::from_rgb(200, 80, 0))
+                    };
+                    ui.colored_label(color, format!("{text} ({:.1}% vaccinated)", current_vax_fraction * 100.0));
+                }
+                None => {
+                    ui.colored_label(
+                        Color32::from_rgb(200, 40, 40),
+                        "Herd immunity unattainable at these rates (vaccine not protective enough)",
+                    );
+                }
             }
             ui.separator();
 
@@ -336,11 +823,16 @@ impl eframe::App for App {
             ui.label("Tip: Click any square to seed an infection. Adjust sliders and click Populate to re-randomize vaccination.");
         });
 
+        egui::TopBottomPanel::bottom("epidemic_curve").resizable(false).show(ctx, |ui| {
+            ui.label(RichText::new("Epidemic Curve").strong());
+            self.draw_epidemic_curve(ui);
+        });
+
         egui::CentralPanel::default().show(ctx, |ui| {
             self.draw_grid(ui);
         });
 
-        // request continuous repaint so scheduled infections can fire smoothly
+        // request continuous repaint so scheduled transitions can fire smoothly
         ctx.request_repaint_after(Duration::from_millis(16));
     }
 }